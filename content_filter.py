@@ -0,0 +1,117 @@
+#!/usr/bin/env python3
+"""
+Prohibited Content Filter
+=========================
+
+Screens generated events before they can become markets. Each rule category
+either blocks the event outright (e.g. assassination or personal-harm markets)
+or flags it for admin review (e.g. questions about protected characteristics).
+
+Rules can be extended without code changes via CONTENT_RULES_FILE, a JSON file
+of the same shape as DEFAULT_RULES; categories in the file replace the defaults
+of the same name. A rule's optional "exclusions" are cut from the text before
+its patterns run, so known harmless phrasings don't trip it.
+"""
+
+import os
+import re
+import json
+from typing import Dict, List
+
+from dotenv import load_dotenv
+
+load_dotenv()
+
+RULES_FILE = os.getenv("CONTENT_RULES_FILE")
+
+ACTION_BLOCK = "block"
+ACTION_FLAG = "flag"
+
+# Things that get "killed" or "shot" without anyone being hurt ("Will the bill be
+# killed in committee?", "Will the movie be shot in Atlanta?")
+_NON_PERSON = (r"(bill|legislation|measure|amendment|proposal|deal|merger|project|program|plan|policy|"
+               r"feature|product|movie|film|scene|series|show|episode|pilot|video|footage|commercial|documentary)s?")
+
+DEFAULT_RULES: Dict[str, Dict] = {
+    "assassination": {
+        "action": ACTION_BLOCK,
+        "patterns": [
+            r"\bassassinat\w*",
+            r"\b(be|get|gets|got|is|was) (killed|murdered|shot|poisoned)\b",
+            r"\b(kill|kills|killed|killing|murder|murders|murdered|shoot|shoots|shooting of) \w+",
+        ],
+        "exclusions": [
+            rf"\b{_NON_PERSON} (be|get|gets|got|is|was) (killed|shot)\b",
+            rf"\b(kill|kills|killed|killing|shoot|shoots) (the |a |an |its |their )?{_NON_PERSON}\b",
+            r"\b(shoot|shoots|shot) (for|up|past|over)\b",
+        ],
+    },
+    "personal_harm": {
+        "action": ACTION_BLOCK,
+        "patterns": [
+            r"\b(commit|commits|attempt|attempts) suicide\b",
+            r"\bharm (himself|herself|themselves)\b",
+            r"\b(be|get|gets) kidnapp?ed\b",
+            r"\bswat(ted|ting)\b",
+        ],
+    },
+    "death_of_individual": {
+        "action": ACTION_FLAG,
+        "patterns": [r"\bwill [\w .'-]+ die\b", r"\bdeath of\b", r"\bpass(es)? away\b"],
+    },
+    "protected_categories": {
+        "action": ACTION_FLAG,
+        "patterns": [r"\brace of\b", r"\b(ethnicity|religion|religious|sexual orientation|gender identity|disabilit\w+)\b"],
+    },
+}
+
+
+def load_rules() -> Dict[str, Dict]:
+    rules = dict(DEFAULT_RULES)
+    if RULES_FILE and os.path.exists(RULES_FILE):
+        with open(RULES_FILE, 'r', encoding='utf-8') as f:
+            rules.update(json.load(f))
+    for name, rule in rules.items():
+        if rule.get("action") not in (ACTION_BLOCK, ACTION_FLAG):
+            raise ValueError(f"Content rule '{name}' has invalid action {rule.get('action')!r}")
+        patterns = rule.get("patterns")
+        if not isinstance(patterns, list) or not patterns:
+            raise ValueError(f"Content rule '{name}' needs a non-empty list of patterns")
+        for pattern in patterns + list(rule.get("exclusions") or []):
+            try:
+                re.compile(pattern)
+            except (re.error, TypeError) as e:
+                raise ValueError(f"Content rule '{name}' has invalid pattern {pattern!r}: {e}")
+    return rules
+
+
+def check_text(text: str, rules: Dict[str, Dict] = None) -> List[Dict]:
+    """
+    Return every rule category the text matches, with the matching fragment
+    """
+    rules = rules if rules is not None else load_rules()
+    matches = []
+    for name, rule in rules.items():
+        scoped = text
+        for pattern in rule.get("exclusions") or []:
+            scoped = re.sub(pattern, " ", scoped, flags=re.IGNORECASE)
+        for pattern in rule["patterns"]:
+            m = re.search(pattern, scoped, re.IGNORECASE)
+            if m:
+                matches.append({"category": name, "action": rule["action"], "match": m.group(0)})
+                break
+    return matches
+
+
+def check_event(event: Dict, rules: Dict[str, Dict] = None) -> Dict:
+    """
+    Moderate an event's title, description and options.
+    Blocked events must never become markets; flagged ones need an admin.
+    """
+    text = "\n".join([event.get("title") or "", event.get("description") or ""] + list(event.get("options") or []))
+    matches = check_text(text, rules)
+    return {
+        "blocked": any(m["action"] == ACTION_BLOCK for m in matches),
+        "flagged": any(m["action"] == ACTION_FLAG for m in matches),
+        "matches": matches,
+    }
@@ -0,0 +1,126 @@
+#!/usr/bin/env python3
+"""
+Date Normalization
+==================
+
+Scraped and AI-generated dates arrive as free text ("Jan 3rd, 7pm ET",
+"November 1, 2025", "2025-12-31T23:59:00-05:00"). This module turns them into
+UTC timestamps the blockchain can use as a market close time.
+
+    normalize_date("2025-11-01T23:59:00-05:00")  ->  1762059540
+    normalize_date("sometime soon")              ->  None
+"""
+
+import re
+import calendar
+from datetime import datetime, timezone
+from typing import Dict, Optional
+
+from dateutil import parser as date_parser
+from dateutil import tz
+
+# US-centric abbreviations seen on event and ticketing sites. "ET"/"PT" etc.
+# follow daylight saving, so they map to zones rather than fixed offsets.
+TZINFOS = {
+    "UTC": tz.UTC, "GMT": tz.UTC, "Z": tz.UTC,
+    "ET": tz.gettz("America/New_York"), "EST": tz.gettz("America/New_York"), "EDT": tz.gettz("America/New_York"),
+    "CT": tz.gettz("America/Chicago"), "CST": tz.gettz("America/Chicago"), "CDT": tz.gettz("America/Chicago"),
+    "MT": tz.gettz("America/Denver"), "MST": tz.gettz("America/Denver"), "MDT": tz.gettz("America/Denver"),
+    "PT": tz.gettz("America/Los_Angeles"), "PST": tz.gettz("America/Los_Angeles"), "PDT": tz.gettz("America/Los_Angeles"),
+    "BST": tz.gettz("Europe/London"), "CET": tz.gettz("Europe/Paris"), "CEST": tz.gettz("Europe/Paris"),
+}
+
+# Dates without a zone are assumed to be US Eastern, matching the mock events
+DEFAULT_TZ = tz.gettz("America/New_York")
+
+_ORDINAL = re.compile(r"\b(\d{1,2})(st|nd|rd|th)\b", re.IGNORECASE)
+_YEAR = re.compile(r"\b(19|20)\d{2}\b")
+_TIME = re.compile(r"\d{1,2}:\d{2}|\b\d{1,2}\s*(am|pm)\b", re.IGNORECASE)
+_MONTH = r"(jan(uary)?|feb(ruary)?|mar(ch)?|apr(il)?|may|june?|july?|aug(ust)?|sept?(ember)?|oct(ober)?|nov(ember)?|dec(ember)?)"
+_MONTH_DAY = re.compile(
+    rf"\b{_MONTH}\.?\s+\d{{1,2}}(st|nd|rd|th)?\b|\b\d{{1,2}}(st|nd|rd|th)?\s+(of\s+)?{_MONTH}\b",
+    re.IGNORECASE,
+)
+_MONTH_YEAR = re.compile(rf"\b{_MONTH}\.?,?\s+(19|20)\d{{2}}\b", re.IGNORECASE)
+_NUMERIC_DATE = re.compile(r"\b\d{4}-\d{1,2}-\d{1,2}|\b\d{1,2}/\d{1,2}(/\d{2,4})?\b")
+_NOISE = re.compile(r"\b(at|on|by|the|before|after)\b|@", re.IGNORECASE)
+
+
+def _clean(raw: str) -> str:
+    text = _ORDINAL.sub(r"\1", raw)
+    text = _NOISE.sub(" ", text)
+    return re.sub(r"\s+", " ", text).strip(" ,")
+
+
+def parse_date(raw: Optional[str], now: Optional[datetime] = None) -> Optional[datetime]:
+    """
+    Parse a free-text date into an aware UTC datetime, or None if it can't be parsed.
+    Dates without a year roll forward to the next occurrence after `now`, dates
+    without a time close at the end of that day, and month-only dates close at
+    the end of that month.
+    """
+    # fuzzy parsing turns any stray number into a day of the month ("Top 10 teams" -> the 10th)
+    # and any month word into a date ("price may rise" -> May), so a month name only
+    # counts next to a day or a year
+    if not raw:
+        return None
+    has_day = bool(_MONTH_DAY.search(raw) or _NUMERIC_DATE.search(raw))
+    if not has_day and not _MONTH_YEAR.search(raw):
+        return None
+
+    now = now or datetime.now(timezone.utc)
+    default = now.astimezone(DEFAULT_TZ).replace(hour=0, minute=0, second=0, microsecond=0)
+    if not has_day:
+        # "December 2025" would otherwise take today's day of the month
+        default = default.replace(day=1)
+
+    try:
+        parsed = date_parser.parse(_clean(raw), default=default.replace(tzinfo=None), tzinfos=TZINFOS, fuzzy=True)
+    except (ValueError, OverflowError):
+        return None
+
+    if not has_day:
+        # Month-only dates close at the end of the month
+        parsed = parsed.replace(day=calendar.monthrange(parsed.year, parsed.month)[1])
+    if not _TIME.search(raw):
+        parsed = parsed.replace(hour=23, minute=59)
+    if parsed.tzinfo is None:
+        parsed = parsed.replace(tzinfo=DEFAULT_TZ)
+
+    if not _YEAR.search(raw) and parsed < now:
+        try:
+            parsed = parsed.replace(year=parsed.year + 1)
+        except ValueError:
+            # Feb 29 has no next-year occurrence
+            return None
+
+    return parsed.astimezone(timezone.utc)
+
+
+def normalize_date(raw: Optional[str], now: Optional[datetime] = None) -> Optional[int]:
+    """
+    Convert a free-text date into a UTC unix timestamp (seconds)
+    """
+    parsed = parse_date(raw, now=now)
+    return int(parsed.timestamp()) if parsed else None
+
+
+def normalize_event_dates(event: Dict, now: Optional[datetime] = None) -> Dict:
+    """
+    Fill `close_time` (UTC timestamp) from the event's resolution_date and flag
+    events whose date can't be parsed, or has already passed, so they are held
+    for review.
+    """
+    now = now or datetime.now(timezone.utc)
+    close_time = normalize_date(event.get("resolution_date"), now=now)
+    event["close_time"] = close_time
+    event["date_unparsed"] = close_time is None
+    event["date_in_past"] = close_time is not None and close_time < now.timestamp()
+    return event
+
+
+def has_usable_close_time(event: Dict) -> bool:
+    """
+    True when the event can close automatically: parsed and still in the future
+    """
+    return bool(event.get("close_time")) and not event.get("date_unparsed") and not event.get("date_in_past")
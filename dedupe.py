@@ -0,0 +1,119 @@
+#!/usr/bin/env python3
+"""
+Duplicate Article Detection
+===========================
+
+The same story syndicated across feeds would otherwise produce the same
+prediction event over and over. Articles are fingerprinted two ways:
+
+- canonical URL (tracking params, fragments, "www." and trailing slashes stripped)
+- MinHash signature of the body text, so reworded syndicated copies still match
+
+Fingerprints are kept in a persistent seen-set so repeats are skipped across runs.
+"""
+
+import os
+import re
+import json
+import hashlib
+import threading
+from typing import Dict, List, Optional
+from urllib.parse import urlparse, urlunparse, parse_qsl, urlencode
+
+from dotenv import load_dotenv
+
+load_dotenv()
+
+SEEN_FILE = os.getenv("SEEN_ARTICLES_FILE", os.path.join("logs", "seen_articles.json"))
+SIMILARITY_THRESHOLD = float(os.getenv("DUPLICATE_SIMILARITY_THRESHOLD", "0.8"))
+
+NUM_HASHES = 64
+SHINGLE_SIZE = 5
+TRACKING_PARAMS = {"fbclid", "gclid", "mc_cid", "mc_eid", "ref", "ref_src", "igshid"}
+
+_lock = threading.Lock()
+
+
+def canonicalize_url(url: str) -> str:
+    """
+    Normalize a URL so the same article reached via different links compares equal
+    """
+    parts = urlparse(url.strip())
+    host = parts.netloc.lower()
+    if host.startswith("www."):
+        host = host[4:]
+    query = [(k, v) for k, v in parse_qsl(parts.query) if not k.lower().startswith("utm_") and k.lower() not in TRACKING_PARAMS]
+    path = parts.path.rstrip("/") or "/"
+    return urlunparse(("https", host, path, "", urlencode(sorted(query)), ""))
+
+
+def _shingles(text: str) -> set:
+    words = re.findall(r"\w+", text.lower())
+    if len(words) < SHINGLE_SIZE:
+        return {" ".join(words)} if words else set()
+    return {" ".join(words[i:i + SHINGLE_SIZE]) for i in range(len(words) - SHINGLE_SIZE + 1)}
+
+
+def minhash(text: str) -> List[int]:
+    """
+    MinHash signature over word 5-gram shingles; one seeded blake2b per hash slot
+    """
+    shingles = _shingles(text)
+    if not shingles:
+        return []
+    signature = []
+    for seed in range(NUM_HASHES):
+        salt = seed.to_bytes(16, "little")
+        signature.append(min(
+            int.from_bytes(hashlib.blake2b(s.encode("utf-8"), digest_size=8, salt=salt).digest(), "little")
+            for s in shingles
+        ))
+    return signature
+
+
+def similarity(a: List[int], b: List[int]) -> float:
+    """
+    Estimated Jaccard similarity of two MinHash signatures
+    """
+    if not a or not b or len(a) != len(b):
+        return 0.0
+    return sum(1 for x, y in zip(a, b) if x == y) / len(a)
+
+
+def load_seen(path: Optional[str] = None) -> List[Dict]:
+    path = path or SEEN_FILE
+    if not os.path.exists(path):
+        return []
+    with open(path, 'r', encoding='utf-8') as f:
+        return json.load(f)
+
+
+def find_duplicate(url: str, content: str, path: Optional[str] = None) -> Optional[Dict]:
+    """
+    Return the previously seen article this one duplicates, if any
+    """
+    canonical = canonicalize_url(url)
+    signature = minhash(content)
+    for seen in load_seen(path):
+        if seen["canonical_url"] == canonical:
+            return seen
+        if similarity(signature, seen["minhash"]) >= SIMILARITY_THRESHOLD:
+            return seen
+    return None
+
+
+def mark_seen(url: str, content: str, run_id: Optional[str] = None, path: Optional[str] = None) -> Dict:
+    """
+    Add an article's fingerprint to the persistent seen-set
+    """
+    path = path or SEEN_FILE
+    record = {"canonical_url": canonicalize_url(url), "minhash": minhash(content), "run_id": run_id}
+    with _lock:
+        seen = load_seen(path)
+        seen.append(record)
+        os.makedirs(os.path.dirname(path) or ".", exist_ok=True)
+        tmp = f"{path}.tmp"
+        with open(tmp, 'w', encoding='utf-8') as f:
+            json.dump(seen, f)
+        os.replace(tmp, path)
+    return record
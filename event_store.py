@@ -0,0 +1,151 @@
+#!/usr/bin/env python3
+"""
+Scraped Event Store
+===================
+
+Persists every event the agent derives from a URL so nothing vanishes between
+scraping and market creation. Events move through a small review workflow:
+
+    new -> approved -> converted
+    new -> rejected
+    draft (below the quality threshold) -> approved | rejected
+    rejected (by the content filter) -> appealed -> approved | rejected  (one appeal per event)
+
+Only sources listed in TRUSTED_SOURCES are converted to markets automatically;
+everything else waits in the queue for an admin.
+"""
+
+import os
+import json
+import time
+import uuid
+import threading
+from typing import Dict, List, Optional
+from dotenv import load_dotenv
+
+from rendering import parse_domain_list, domain_matches
+
+load_dotenv()
+
+EVENTS_FILE = os.getenv("SCRAPED_EVENTS_FILE", os.path.join("logs", "scraped_events.json"))
+TRUSTED_SOURCES = parse_domain_list(os.getenv("TRUSTED_SOURCES", ""))
+
+STATUS_NEW = "new"
+STATUS_APPROVED = "approved"
+STATUS_REJECTED = "rejected"
+STATUS_CONVERTED = "converted"
+STATUS_APPEALED = "appealed"
+STATUS_DRAFT = "draft"
+STATUSES = (STATUS_NEW, STATUS_APPROVED, STATUS_REJECTED, STATUS_CONVERTED, STATUS_APPEALED, STATUS_DRAFT)
+
+_lock = threading.Lock()
+
+
+def is_trusted_source(url: str) -> bool:
+    """
+    Check whether a URL belongs to a source explicitly marked as trusted
+    """
+    return domain_matches(url, TRUSTED_SOURCES)
+
+
+def load_events(path: Optional[str] = None) -> List[Dict]:
+    path = path or EVENTS_FILE
+    if not os.path.exists(path):
+        return []
+    with open(path, 'r', encoding='utf-8') as f:
+        return json.load(f)
+
+
+def save_events(events: List[Dict], path: Optional[str] = None) -> None:
+    path = path or EVENTS_FILE
+    os.makedirs(os.path.dirname(path) or ".", exist_ok=True)
+    tmp = f"{path}.tmp"
+    with open(tmp, 'w', encoding='utf-8') as f:
+        json.dump(events, f, ensure_ascii=False, indent=2)
+    os.replace(tmp, path)
+
+
+def record_event(event: Dict, run_id: Optional[str] = None, path: Optional[str] = None,
+                 moderation: Optional[Dict] = None, quality: Optional[Dict] = None) -> Dict:
+    """
+    Persist a freshly scraped event and return the stored record. Events blocked
+    by the content filter are stored already rejected so they can be appealed;
+    events below the quality threshold are stored as drafts.
+    """
+    now = int(time.time())
+    if moderation and moderation.get("blocked"):
+        status = STATUS_REJECTED
+    elif quality and quality.get("draft"):
+        status = STATUS_DRAFT
+    else:
+        status = STATUS_NEW
+    record = {
+        "id": f"evt_{uuid.uuid4().hex[:12]}",
+        "run_id": run_id,
+        "status": status,
+        "trusted_source": is_trusted_source(event.get("source_url", "")),
+        "created_at": now,
+        "updated_at": now,
+        "market_id": None,
+        "moderation": moderation,
+        "quality": quality,
+        "event": event,
+    }
+    with _lock:
+        events = load_events(path)
+        events.append(record)
+        save_events(events, path)
+    return record
+
+
+def list_events(status: Optional[str] = None, path: Optional[str] = None) -> List[Dict]:
+    events = load_events(path)
+    if status:
+        events = [e for e in events if e["status"] == status]
+    return events
+
+
+def get_event(event_id: str, path: Optional[str] = None) -> Optional[Dict]:
+    return next((e for e in load_events(path) if e["id"] == event_id), None)
+
+
+def update_status(event_id: str, status: str, market_id: Optional[str] = None, path: Optional[str] = None) -> Optional[Dict]:
+    """
+    Move an event to a new review status; returns the updated record or None if unknown
+    """
+    if status not in STATUSES:
+        raise ValueError(f"Unknown status '{status}' (expected one of {', '.join(STATUSES)})")
+
+    with _lock:
+        events = load_events(path)
+        for record in events:
+            if record["id"] == event_id:
+                record["status"] = status
+                record["updated_at"] = int(time.time())
+                if market_id:
+                    record["market_id"] = market_id
+                save_events(events, path)
+                return record
+    return None
+
+
+def appeal(event_id: str, path: Optional[str] = None) -> Optional[Dict]:
+    """
+    Send an event rejected by the content filter back to admins for a second look.
+    Each event gets one appeal; an admin rejection after that is final.
+    """
+    with _lock:
+        events = load_events(path)
+        record = next((e for e in events if e["id"] == event_id), None)
+        if not record:
+            return None
+        if record["status"] != STATUS_REJECTED or not (record.get("moderation") or {}).get("blocked"):
+            raise ValueError(f"Event {event_id} was not rejected by the content filter")
+        if record.get("appealed_at"):
+            raise ValueError(f"Event {event_id} was already appealed")
+        now = int(time.time())
+        record["status"] = STATUS_APPEALED
+        record["appealed_at"] = now
+        record["updated_at"] = now
+        save_events(events, path)
+        return record
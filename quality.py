@@ -0,0 +1,92 @@
+#!/usr/bin/env python3
+"""
+Market Quality Scoring
+======================
+
+Auto-generated events vary wildly in quality. Each event gets a 0-1 score from:
+
+- source confidence     (the analyzer's own confidence)
+- title parsability     (a concrete yes/no style question of sensible length)
+- resolvability         (has a parseable, future close date and some resolution context)
+- source reputation     (trusted sources score higher)
+
+Events below MARKET_QUALITY_THRESHOLD are held as drafts for admin approval
+instead of going live.
+"""
+
+import os
+import re
+from typing import Dict
+
+from dotenv import load_dotenv
+
+from date_normalizer import has_usable_close_time
+
+load_dotenv()
+
+QUALITY_THRESHOLD = float(os.getenv("MARKET_QUALITY_THRESHOLD", "0.6"))
+
+WEIGHTS = {
+    "confidence": 0.30,
+    "title": 0.25,
+    "resolvability": 0.30,
+    "reputation": 0.15,
+}
+
+_QUESTION_START = re.compile(r"^(will|is|are|does|do|did|has|have|can|should|which|who|what|when|how many|how much)\b", re.IGNORECASE)
+# Titles the analyzers produce when they couldn't find a real question
+_GENERIC_TITLE = re.compile(r"^prediction( about)?:|will this article|occur as predicted", re.IGNORECASE)
+# Titles the analyzers fall back to when the AI call itself failed or is disabled
+_FALLBACK_TITLE = re.compile(r"^prediction( about)?:", re.IGNORECASE)
+
+
+def is_ai_fallback(event: Dict) -> bool:
+    """
+    True when the event is the analyzer's stand-in for a failed AI call, so a
+    retry may still produce the real event
+    """
+    return bool(_FALLBACK_TITLE.search((event.get("title") or "").strip()))
+
+
+def title_score(title: str) -> float:
+    title = (title or "").strip()
+    if not title or _GENERIC_TITLE.search(title):
+        return 0.0
+    score = 0.0
+    if _QUESTION_START.search(title):
+        score += 0.4
+    if title.endswith("?"):
+        score += 0.3
+    if 20 <= len(title) <= 150:
+        score += 0.3
+    return score
+
+
+def resolvability_score(event: Dict) -> float:
+    score = 0.0
+    if has_usable_close_time(event):
+        score += 0.6
+    if len((event.get("description") or "").strip()) >= 40:
+        score += 0.2
+    if len(event.get("options") or []) >= 2:
+        score += 0.2
+    return score
+
+
+def score_event(event: Dict, trusted_source: bool = False) -> Dict:
+    """
+    Score an event; returns the overall score, per-component breakdown and
+    whether it should be held as a draft
+    """
+    components = {
+        "confidence": max(0.0, min(1.0, float(event.get("confidence") or 0.0))),
+        "title": title_score(event.get("title", "")),
+        "resolvability": resolvability_score(event),
+        "reputation": 1.0 if trusted_source else 0.5,
+    }
+    score = round(sum(WEIGHTS[k] * v for k, v in components.items()), 3)
+    return {
+        "score": score,
+        "components": components,
+        "draft": score < QUALITY_THRESHOLD,
+    }
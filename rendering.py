@@ -0,0 +1,56 @@
+#!/usr/bin/env python3
+"""
+Source Domains & JS Rendering
+=============================
+
+Shared by the CLI (serve_frontend.py) and the HTTP service (url_scraper.py) so
+both decide the same way which domains are client-side rendered, and fetch
+those through the remote headless-browser service.
+"""
+
+import os
+from typing import List
+from urllib.parse import urlparse
+
+import requests
+from dotenv import load_dotenv
+
+load_dotenv()
+
+
+def parse_domain_list(value: str) -> List[str]:
+    """
+    Parse a comma-separated env value into lowercase domains
+    """
+    return [d.strip().lower() for d in (value or "").split(",") if d.strip()]
+
+
+def domain_matches(url: str, domains: List[str]) -> bool:
+    """
+    Check whether a URL's host is one of the domains or a subdomain of one
+    """
+    host = urlparse(url).netloc.lower()
+    return any(host == d or host.endswith("." + d) for d in domains)
+
+
+RENDER_SERVICE_URL = os.getenv("RENDER_SERVICE_URL")
+JS_RENDERED_DOMAINS = parse_domain_list(os.getenv("JS_RENDERED_DOMAINS", ""))
+
+
+def needs_js_render(url: str) -> bool:
+    """
+    Check whether a URL's domain is configured as client-side rendered
+    """
+    return domain_matches(url, JS_RENDERED_DOMAINS)
+
+
+def fetch_rendered_html(url: str, timeout: int = 60, stream: bool = False) -> requests.Response:
+    """
+    Fetch fully rendered HTML from the remote rendering service (headless browser)
+    """
+    if not RENDER_SERVICE_URL:
+        raise RuntimeError("JS rendering requested but RENDER_SERVICE_URL is not set")
+
+    r = requests.post(RENDER_SERVICE_URL, json={"url": url}, timeout=timeout, stream=stream)
+    r.raise_for_status()
+    return r
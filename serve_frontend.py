@@ -24,6 +24,14 @@ from bs4 import BeautifulSoup
 from pydantic import BaseModel
 from dotenv import load_dotenv
 
+import content_filter
+import rendering
+import dedupe
+import event_store
+import quality
+import snapshots
+from date_normalizer import normalize_event_dates, has_usable_close_time
+
 try:
     import openai
 except Exception:
@@ -49,13 +57,22 @@ class PredictionEvent(BaseModel):
     options: List[str]
     confidence: float
     source_url: str
-    resolution_date: str  # ISO format with timezone
+    resolution_date: Optional[str] = None  # As scraped/generated, ideally ISO with timezone
+    close_time: Optional[int] = None  # Normalized UTC unix timestamp
+    date_unparsed: bool = False  # True when resolution_date couldn't be normalized
+    date_in_past: bool = False  # True when the normalized close time has already passed
+    quality_score: Optional[float] = None  # 0-1, below MARKET_QUALITY_THRESHOLD is held as a draft
 
 
-def scrape_content(url: str, max_retries: int = 3) -> Dict:
+def scrape_content(url: str, max_retries: int = 3, render_js: Optional[bool] = None) -> Dict:
     """
-    Scrape content from a URL with retry logic and better headers
+    Scrape content from a URL with retry logic and better headers.
+    React/Vue sites are fetched through the rendering service when render_js is set
+    or the domain is listed in JS_RENDERED_DOMAINS.
     """
+    if render_js is None:
+        render_js = rendering.needs_js_render(url)
+
     headers = {
         "User-Agent": "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
         "Accept": "text/html,application/xhtml+xml,application/xml;q=0.9,image/webp,*/*;q=0.8",
@@ -67,9 +84,12 @@ def scrape_content(url: str, max_retries: int = 3) -> Dict:
     
     for attempt in range(max_retries):
         try:
-            r = requests.get(url, headers=headers, timeout=20, allow_redirects=True)
-            r.raise_for_status()
-            soup = BeautifulSoup(r.content, "html.parser")
+            if render_js:
+                html = rendering.fetch_rendered_html(url).content
+            else:
+                r = requests.get(url, headers=headers, timeout=20, allow_redirects=True)
+                r.raise_for_status()
+                html = r.content
             break
         except (requests.exceptions.ConnectionError, ConnectionResetError) as e:
             if attempt < max_retries - 1:
@@ -84,7 +104,7 @@ def scrape_content(url: str, max_retries: int = 3) -> Dict:
             return None
     
     try:
-        soup = BeautifulSoup(r.content, "html.parser")
+        soup = BeautifulSoup(html, "html.parser")
 
         for el in soup(['script', 'style', 'nav', 'header', 'footer', 'aside', 'form']):
             el.decompose()
@@ -180,7 +200,8 @@ def analyze_with_ai(scraped: Dict, category: str, ai_mock: bool = False) -> Pred
         prompt = (
             f"Create a prediction-market-ready JSON from the article.\nTitle: {scraped.get('title')}\n\n"
             f"Content excerpt: {scraped.get('content','')[:2000]}\n\n"
-            "Return only a JSON object with keys: title, description, category, options (list), confidence (0-1)."
+            "Return only a JSON object with keys: title, description, category, options (list), confidence (0-1), "
+            "resolution_date (when the outcome is known, ISO 8601 with timezone)."
         )
 
         resp = openai.ChatCompletion.create(
@@ -209,7 +230,8 @@ def analyze_with_ai(scraped: Dict, category: str, ai_mock: bool = False) -> Pred
             category=parsed.get('category', category),
             options=parsed['options'],
             confidence=float(parsed.get('confidence', 0.8)),
-            source_url=scraped.get('url')
+            source_url=scraped.get('url'),
+            resolution_date=parsed.get('resolution_date')
         )
     except Exception as e:
         print(f"[analyze_with_ai] AI error: {e}")
@@ -262,9 +284,10 @@ def test_blockchain_connection() -> Dict:
         }
 
 
-def create_market(event: PredictionEvent, dry_run: bool = True) -> Dict:
+def create_market(event: PredictionEvent, dry_run: bool = True, override_filter: bool = False) -> Dict:
     """
-    Enhanced blockchain market creation with detailed response and error handling
+    Enhanced blockchain market creation with detailed response and error handling.
+    Events blocked by the content filter are refused unless an admin upheld an appeal.
     """
     from urllib.parse import urlparse
     
@@ -285,10 +308,23 @@ def create_market(event: PredictionEvent, dry_run: bool = True) -> Dict:
             "options": event.options,
             "confidence": event.confidence,
             "source_url": event.source_url,
-            "resolution_date": event.resolution_date
+            "resolution_date": event.resolution_date,
+            "close_time": event.close_time,
+            "quality_score": event.quality_score
         }
     }
 
+    moderation = content_filter.check_event(payload["event"])
+    if moderation["blocked"] and not override_filter:
+        categories = ", ".join(m["category"] for m in moderation["matches"] if m["action"] == content_filter.ACTION_BLOCK)
+        return {
+            "success": False,
+            "error": f"Blocked by content filter ({categories})",
+            "mode": "moderation",
+            "moderation": moderation,
+            "payload": payload
+        }
+
     # Return simulation if dry run or blockchain posting is disabled
     if not ALLOW_CREATE_MARKET or dry_run:
         sim_id = f"SIM-{int(time.time())}"
@@ -376,14 +412,14 @@ def create_market(event: PredictionEvent, dry_run: bool = True) -> Dict:
         }
 
 
-def run_pipeline(url: str, category: str = "tech", create_market_flag: bool = False, ai_mock: bool = False, save_dir: Optional[str] = "logs") -> Optional[Dict]:
+def run_pipeline(url: str, category: str = "tech", create_market_flag: bool = False, ai_mock: bool = False, save_dir: Optional[str] = "logs", render_js: Optional[bool] = None, skip_duplicates: bool = True) -> Optional[Dict]:
     os.makedirs(save_dir, exist_ok=True)
     stamp = int(time.time())
     run_id = f"run_{stamp}"
     out = {"run_id": run_id, "url": url, "steps": []}
 
     # Step 1: Scrape content
-    scraped = scrape_content(url)
+    scraped = scrape_content(url, render_js=render_js)
     if not scraped:
         print("❌ Failed to scrape content")
         return None
@@ -399,9 +435,26 @@ def run_pipeline(url: str, category: str = "tech", create_market_flag: bool = Fa
         print(f"❌ Content too short ({content_length} chars) - no meaningful event can be derived")
         return None
 
-    # Step 3: Analyze with AI
+    # Step 3: Skip articles already seen (same canonical URL or near-identical body)
+    if skip_duplicates:
+        duplicate = dedupe.find_duplicate(url, scraped['content'])
+        if duplicate:
+            print(f"⏭️ Duplicate of {duplicate['canonical_url']} (run {duplicate['run_id']}) - skipping")
+            out['duplicate_of'] = duplicate['canonical_url']
+            out['steps'].append('duplicate')
+            return out
+
+    # Step 4: Analyze with AI
     try:
         event = analyze_with_ai(scraped, category, ai_mock=ai_mock)
+        event = PredictionEvent(**normalize_event_dates(event.model_dump()))
+        if event.date_unparsed:
+            print(f"⚠️ Could not parse resolution date {event.resolution_date!r} - event flagged for review")
+        elif event.date_in_past:
+            print(f"⚠️ Resolution date {event.resolution_date!r} has already passed - event flagged for review")
+        out['quality'] = quality.score_event(event.model_dump(), trusted_source=event_store.is_trusted_source(url))
+        event.quality_score = out['quality']['score']
+        out['moderation'] = content_filter.check_event(event.model_dump())
         out['event'] = json.loads(event.model_dump_json())
         out['steps'].append('analyzed')
         with open(os.path.join(save_dir, f"{run_id}_event.json"), 'w', encoding='utf-8') as f:
@@ -410,14 +463,47 @@ def run_pipeline(url: str, category: str = "tech", create_market_flag: bool = Fa
         print(f"❌ Failed to analyze content: {e}")
         return None
 
-    # Step 4: Create market if requested
+    # Step 5: Persist the event for review
+    record = event_store.record_event(out['event'], run_id=run_id, moderation=out['moderation'], quality=out['quality'])
+    out['scraped_event_id'] = record['id']
+    out['steps'].append('recorded')
+
+    # Only a real analysis retires the article; after an AI failure a retry should still go through
+    if not quality.is_ai_fallback(out['event']):
+        dedupe.mark_seen(url, scraped['content'], run_id=run_id)
+
+    if out['moderation']['blocked']:
+        categories = ", ".join(m['category'] for m in out['moderation']['matches'])
+        out['steps'].append('blocked')
+        print(f"🚫 Event {record['id']} blocked by content filter ({categories}) - can be appealed with --appeal")
+        return out
+
+    # Step 6: Create market if requested - only trusted, dated, good-quality events convert automatically
     if create_market_flag:
+        if not record['trusted_source']:
+            out['steps'].append('queued_for_review')
+            print(f"🕒 {scraped['domain']} is not a trusted source - event {record['id']} queued for admin review")
+            return out
+        if not has_usable_close_time(event.model_dump()):
+            out['steps'].append('queued_for_review')
+            print(f"🕒 No usable future close time - event {record['id']} queued for admin review")
+            return out
+        if out['moderation']['flagged']:
+            out['steps'].append('queued_for_review')
+            print(f"🕒 Event {record['id']} flagged by content filter - queued for admin review")
+            return out
+        if out['quality']['draft']:
+            out['steps'].append('queued_for_review')
+            print(f"🕒 Quality score {event.quality_score} below {quality.QUALITY_THRESHOLD} - event {record['id']} held as draft")
+            return out
+
         market_result = create_market(event, dry_run=not ALLOW_CREATE_MARKET)
         out['market_result'] = market_result
         
         if market_result['success']:
             out['market_id'] = market_result['market_id']
             out['steps'].append('market_created')
+            event_store.update_status(record['id'], event_store.STATUS_CONVERTED, market_id=market_result['market_id'])
             print(f"✅ {market_result['message']}")
         else:
             out['steps'].append('market_failed')
@@ -426,7 +512,32 @@ def run_pipeline(url: str, category: str = "tech", create_market_flag: bool = Fa
     return out
 
 
-def parse_url(url: str, ai_mock: bool = False, create_market_flag: bool = False) -> Dict:
+def review_event(event_id: str, approve: bool) -> Dict:
+    """
+    Approve (and convert to a market) or reject a queued scraped event
+    """
+    record = event_store.get_event(event_id)
+    if not record:
+        return {"success": False, "error": f"Unknown scraped event: {event_id}"}
+    if record['status'] in (event_store.STATUS_CONVERTED, event_store.STATUS_REJECTED):
+        return {"success": False, "error": f"Event {event_id} already {record['status']}"}
+
+    if not approve:
+        record = event_store.update_status(event_id, event_store.STATUS_REJECTED)
+        return {"success": True, "event": record}
+
+    # A blocked event can only reach approval through an upheld appeal
+    upheld_appeal = bool((record.get('moderation') or {}).get('blocked'))
+    event_store.update_status(event_id, event_store.STATUS_APPROVED)
+    market_result = create_market(PredictionEvent(**record['event']), dry_run=not ALLOW_CREATE_MARKET, override_filter=upheld_appeal)
+    if market_result['success']:
+        record = event_store.update_status(event_id, event_store.STATUS_CONVERTED, market_id=market_result['market_id'])
+    else:
+        record = event_store.get_event(event_id)
+    return {"success": market_result['success'], "event": record, "market_result": market_result}
+
+
+def parse_url(url: str, ai_mock: bool = False, create_market_flag: bool = False, render_js: Optional[bool] = None, skip_duplicates: bool = True) -> Dict:
     """
     Parse a single URL and return event data or 'No event found'
     """
@@ -437,7 +548,9 @@ def parse_url(url: str, ai_mock: bool = False, create_market_flag: bool = False)
             url=url, 
             ai_mock=ai_mock, 
             create_market_flag=create_market_flag,
-            save_dir="logs"
+            save_dir="logs",
+            render_js=render_js,
+            skip_duplicates=skip_duplicates
         )
         
         if result and result.get('event'):
@@ -452,12 +565,23 @@ def parse_url(url: str, ai_mock: bool = False, create_market_flag: bool = False)
                 "run_id": result.get('run_id')
             }
             
+            if result.get('scraped_event_id'):
+                response['scraped_event_id'] = result['scraped_event_id']
+
             # Include market creation results if attempted
             if create_market_flag and 'market_result' in result:
                 response['market_result'] = result['market_result']
                 response['market_id'] = result.get('market_id')
                 
             return response
+        elif result and result.get('duplicate_of'):
+            return {
+                "status": "success",
+                "event_found": False,
+                "message": f"Duplicate article - already seen as {result['duplicate_of']}",
+                "duplicate_of": result['duplicate_of'],
+                "source_url": url
+            }
         else:
             print("❌ No event found")
             return {
@@ -488,6 +612,15 @@ def main():
     p.add_argument('--json', action='store_true', help='Output only JSON result')
     p.add_argument('--test-blockchain', action='store_true', help='Test blockchain connection')
     p.add_argument('--enable-blockchain', action='store_true', help='Enable real blockchain posting (sets ALLOW_CREATE_MARKET=1)')
+    p.add_argument('--list-events', action='store_true', help='List persisted scraped events')
+    p.add_argument('--status', type=str, choices=event_store.STATUSES, help='Filter --list-events by review status')
+    p.add_argument('--approve', type=str, metavar='EVENT_ID', help='Approve a queued event and create its market')
+    p.add_argument('--reject', type=str, metavar='EVENT_ID', help='Reject a queued event')
+    p.add_argument('--appeal', type=str, metavar='EVENT_ID', help='Appeal an event rejected by the content filter')
+    p.add_argument('--snapshot', type=str, metavar='URL', help='Capture a snapshot of a resolution source URL')
+    p.add_argument('--market-id', type=str, help='Market the --snapshot belongs to')
+    p.add_argument('--allow-duplicates', action='store_true', help='Process the URL even if the article was already seen')
+    p.add_argument('--render-js', action='store_true', help='Fetch the page through RENDER_SERVICE_URL (for React/Vue sites)')
     args = p.parse_args()
 
     # Enable blockchain posting if requested
@@ -510,16 +643,63 @@ def main():
                 print(f"❌ Blockchain test failed: {test_result['error']}")
         exit(0)
 
+    # Capture a resolution source snapshot (run at resolution time)
+    if args.snapshot:
+        meta = snapshots.capture_snapshot(args.snapshot, market_id=args.market_id, render_js=args.render_js or None)
+        if args.json:
+            print(json.dumps(meta, indent=2))
+        elif meta['error']:
+            print(f"⚠️ Source unavailable, recorded as {meta['snapshot_id']}: {meta['error']}")
+        else:
+            print(f"📸 Snapshot {meta['snapshot_id']} (HTTP {meta['status_code']}, sha256 {meta['content_hash'][:16]}…)")
+            if meta['truncated']:
+                print(f"⚠️ Page exceeded {snapshots.SNAPSHOT_MAX_BYTES} bytes - snapshot truncated")
+        exit(0)
+
+    # Review queue commands
+    if args.list_events:
+        events = event_store.list_events(args.status)
+        if args.json:
+            print(json.dumps(events, indent=2))
+        else:
+            for e in events:
+                trusted = "trusted" if e['trusted_source'] else "untrusted"
+                print(f"{e['id']}  [{e['status']}]  ({trusted})  {e['event']['title']}")
+            print(f"\n{len(events)} event(s)")
+        exit(0)
+
+    if args.appeal:
+        try:
+            record = event_store.appeal(args.appeal)
+        except ValueError as e:
+            print(f"❌ {e}")
+            exit(1)
+        if not record:
+            print(f"❌ Unknown scraped event: {args.appeal}")
+            exit(1)
+        print(f"✅ Event {record['id']} is now {record['status']} and awaits admin review")
+        exit(0)
+
+    if args.approve or args.reject:
+        review = review_event(args.approve or args.reject, approve=bool(args.approve))
+        if args.json:
+            print(json.dumps(review, indent=2))
+        elif review['success']:
+            print(f"✅ Event {review['event']['id']} is now {review['event']['status']}")
+        else:
+            print(f"❌ {review.get('error') or review['market_result'].get('error')}")
+        exit(0 if review['success'] else 1)
+
     # URL is required for parsing
     if not args.url:
-        print("❌ Error: --url is required (or use --test-blockchain)")
+        print("❌ Error: --url is required (or use --test-blockchain / --list-events)")
         p.print_help()
         exit(1)
 
     # Auto-enable ai_mock if no OpenAI key is available
     use_ai_mock = args.ai_mock or not openai
     
-    result = parse_url(args.url, ai_mock=use_ai_mock, create_market_flag=args.create_market)
+    result = parse_url(args.url, ai_mock=use_ai_mock, create_market_flag=args.create_market, render_js=args.render_js or None, skip_duplicates=not args.allow_duplicates)
     
     if args.json:
         print(json.dumps(result, indent=2))
@@ -530,8 +710,15 @@ def main():
             print(f"   Title: {event['title']}")
             print(f"   Category: {event['category']}")
             print(f"   Confidence: {event['confidence']}")
+            print(f"   Quality Score: {event.get('quality_score')}")
             print(f"   Options: {', '.join(event['options'])}")
             print(f"   Resolution Date: {event['resolution_date']}")
+            if event.get('date_unparsed'):
+                print("   ⚠️ Close Time: unparseable date, held for review")
+            elif event.get('date_in_past'):
+                print("   ⚠️ Close Time: date already passed, held for review")
+            else:
+                print(f"   Close Time (UTC): {time.strftime('%Y-%m-%d %H:%M:%S', time.gmtime(event['close_time']))}")
             
             # Show blockchain result if market creation was attempted
             if args.create_market and 'market_result' in result:
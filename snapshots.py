@@ -0,0 +1,136 @@
+#!/usr/bin/env python3
+"""
+Resolution Source Snapshots
+===========================
+
+Captures the page a market cites as its resolution source at the moment the
+market resolves: raw HTML, extracted text, and SHA-256 hashes of both. If the
+page later changes or disappears, the stored snapshot shows what the resolver
+actually saw. Failed fetches and non-2xx responses are recorded too, since
+"the source was gone at resolution time" is itself evidence.
+
+Client-side rendered sources (JS_RENDERED_DOMAINS) are fetched through the
+rendering service, same as when they were scraped. Bodies are capped at
+SNAPSHOT_MAX_BYTES; a snapshot cut off at the cap is marked "truncated".
+"""
+
+import os
+import json
+import time
+import hashlib
+import uuid
+from typing import Dict, Optional, Tuple
+
+import requests
+from bs4 import BeautifulSoup
+from dotenv import load_dotenv
+
+import rendering
+
+load_dotenv()
+
+SNAPSHOT_DIR = os.getenv("SNAPSHOT_DIR", os.path.join("logs", "snapshots"))
+SNAPSHOT_MAX_BYTES = int(os.getenv("SNAPSHOT_MAX_BYTES", str(5 * 1024 * 1024)))
+
+HEADERS = {
+    "User-Agent": "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+    "Accept": "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+}
+
+
+def extract_text(html: bytes) -> str:
+    soup = BeautifulSoup(html, "html.parser")
+    for el in soup(['script', 'style', 'noscript']):
+        el.decompose()
+    lines = [ln.strip() for ln in soup.get_text("\n", strip=True).split('\n') if ln.strip()]
+    return '\n'.join(lines)
+
+
+def read_capped(r: requests.Response, max_bytes: int) -> Tuple[bytes, bool]:
+    """
+    Read a streamed response body up to max_bytes; returns the body and whether it was cut off
+    """
+    body = bytearray()
+    try:
+        for chunk in r.iter_content(chunk_size=64 * 1024):
+            body.extend(chunk)
+            if len(body) > max_bytes:
+                return bytes(body[:max_bytes]), True
+    finally:
+        r.close()
+    return bytes(body), False
+
+
+def capture_snapshot(url: str, market_id: Optional[str] = None, save_dir: Optional[str] = None, render_js: Optional[bool] = None) -> Dict:
+    """
+    Fetch and store a snapshot of a resolution source URL; returns its metadata
+    """
+    save_dir = save_dir or SNAPSHOT_DIR
+    if render_js is None:
+        render_js = rendering.needs_js_render(url)
+    os.makedirs(save_dir, exist_ok=True)
+    snapshot_id = f"snap_{int(time.time())}_{uuid.uuid4().hex[:8]}"
+    meta = {
+        "snapshot_id": snapshot_id,
+        "url": url,
+        "market_id": market_id,
+        "captured_at": int(time.time()),
+        "status_code": None,
+        "final_url": None,
+        "content_hash": None,
+        "text_hash": None,
+        "error": None,
+        "truncated": False,
+    }
+
+    try:
+        if render_js:
+            r = rendering.fetch_rendered_html(url, stream=True)
+            meta["final_url"] = url
+        else:
+            r = requests.get(url, headers=HEADERS, timeout=30, allow_redirects=True, stream=True)
+            meta["final_url"] = r.url
+        meta["status_code"] = r.status_code
+        if not 200 <= r.status_code < 300:
+            # Keep the error page itself - it is what the resolver saw
+            meta["error"] = f"HTTP {r.status_code}"
+        html, meta["truncated"] = read_capped(r, SNAPSHOT_MAX_BYTES)
+    except Exception as e:
+        meta["error"] = str(e)
+        html = None
+
+    if html is not None:
+        text = extract_text(html)
+        meta["content_hash"] = hashlib.sha256(html).hexdigest()
+        meta["text_hash"] = hashlib.sha256(text.encode('utf-8')).hexdigest()
+        with open(os.path.join(save_dir, f"{snapshot_id}.html"), 'wb') as f:
+            f.write(html)
+        with open(os.path.join(save_dir, f"{snapshot_id}.txt"), 'w', encoding='utf-8') as f:
+            f.write(text)
+
+    with open(os.path.join(save_dir, f"{snapshot_id}.json"), 'w', encoding='utf-8') as f:
+        json.dump(meta, f, indent=2)
+    return meta
+
+
+def load_snapshot(snapshot_id: str, save_dir: Optional[str] = None) -> Optional[Dict]:
+    """
+    Load snapshot metadata plus extracted text, verifying the stored HTML still
+    matches its recorded hash
+    """
+    save_dir = save_dir or SNAPSHOT_DIR
+    meta_path = os.path.join(save_dir, f"{os.path.basename(snapshot_id)}.json")
+    if not os.path.exists(meta_path):
+        return None
+    with open(meta_path, 'r', encoding='utf-8') as f:
+        meta = json.load(f)
+
+    html_path = os.path.join(save_dir, f"{meta['snapshot_id']}.html")
+    if meta["content_hash"] and os.path.exists(html_path):
+        with open(html_path, 'rb') as f:
+            meta["verified"] = hashlib.sha256(f.read()).hexdigest() == meta["content_hash"]
+        with open(os.path.join(save_dir, f"{meta['snapshot_id']}.txt"), 'r', encoding='utf-8') as f:
+            meta["text"] = f.read()
+    else:
+        meta["verified"] = False
+    return meta
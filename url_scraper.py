@@ -6,24 +6,35 @@ Scrapes URLs → Extracts events → Creates prediction markets
 
 Usage:
     POST /scrape {"url": "https://example.com"}
+    GET /scrape/events?status=new
+    POST /snapshots {"url": "https://source.example.com", "market_id": "..."}
     GET /health
 """
 
 import os
 import json
 import re
+import hmac
 from typing import Dict, List, Optional
 from urllib.parse import urlparse
 
 import requests
 from bs4 import BeautifulSoup
 from openai import OpenAI
-from fastapi import FastAPI, HTTPException
+from fastapi import Depends, FastAPI, Header, HTTPException
 from fastapi.middleware.cors import CORSMiddleware
 from pydantic import BaseModel, HttpUrl
 import uvicorn
 from dotenv import load_dotenv
 
+import content_filter
+import dedupe
+import rendering
+from date_normalizer import normalize_event_dates, has_usable_close_time
+import event_store
+import quality
+import snapshots
+
 # Load environment
 load_dotenv()
 
@@ -31,6 +42,7 @@ load_dotenv()
 OPENAI_API_KEY = os.getenv("OPENAI_API_KEY")
 BLOCKCHAIN_URL = os.getenv("BLOCKCHAIN_API_URL", "http://localhost:3000")
 PORT = int(os.getenv("AGENT_PORT", "8082"))
+ADMIN_TOKEN = os.getenv("ADMIN_TOKEN")
 
 # Initialize
 openai_client = OpenAI(api_key=OPENAI_API_KEY) if OPENAI_API_KEY else None
@@ -44,6 +56,8 @@ app.add_middleware(CORSMiddleware, allow_origins=["*"], allow_methods=["*"], all
 class URLRequest(BaseModel):
     url: HttpUrl
     category: Optional[str] = "tech"
+    render_js: Optional[bool] = None  # None = decide from JS_RENDERED_DOMAINS
+    allow_duplicates: bool = False
 
 class PredictionEvent(BaseModel):
     title: str
@@ -52,29 +66,46 @@ class PredictionEvent(BaseModel):
     options: List[str]
     confidence: float
     source_url: str
+    resolution_date: Optional[str] = None  # As generated, ideally ISO with timezone
+    close_time: Optional[int] = None  # Normalized UTC unix timestamp
+    date_unparsed: bool = False
+    date_in_past: bool = False
+
+class SnapshotRequest(BaseModel):
+    url: HttpUrl
+    market_id: Optional[str] = None
+    render_js: Optional[bool] = None  # None = decide from JS_RENDERED_DOMAINS
 
 class ScrapeResponse(BaseModel):
     success: bool
     market_id: Optional[str] = None
     event: Optional[PredictionEvent] = None
     message: str
+    scraped_event_id: Optional[str] = None
 
 # ============================================
 # CORE FUNCTIONS
 # ============================================
 
-def scrape_content(url: str) -> Dict:
+def fetch_html(url: str, render_js: bool) -> bytes:
+    """Fetch raw HTML, or fully rendered HTML via the rendering service"""
+    if render_js:
+        return rendering.fetch_rendered_html(url).content
+    headers = {
+        'User-Agent': 'Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36'
+    }
+    response = requests.get(url, headers=headers, timeout=30)
+    response.raise_for_status()
+    return response.content
+
+def scrape_content(url: str, render_js: Optional[bool] = None) -> Dict:
     """Scrape webpage content"""
     try:
         print(f"🔍 Scraping: {url}")
-        headers = {
-            'User-Agent': 'Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36'
-        }
-        
-        response = requests.get(url, headers=headers, timeout=30)
-        response.raise_for_status()
+        if render_js is None:
+            render_js = rendering.needs_js_render(url)
         
-        soup = BeautifulSoup(response.content, 'html.parser')
+        soup = BeautifulSoup(fetch_html(url, render_js), 'html.parser')
         
         # Remove unwanted elements
         for element in soup(['script', 'style', 'nav', 'footer', 'header', 'aside']):
@@ -129,7 +160,8 @@ Return JSON:
     "description": "Brief context in 1-2 sentences",
     "category": "{category}",
     "options": ["Yes", "No"] or ["Option A", "Option B", "Option C"],
-    "confidence": 0.8
+    "confidence": 0.8,
+    "resolution_date": "When the outcome is known, ISO 8601 with timezone"
 }}"""
 
         response = openai_client.chat.completions.create(
@@ -150,7 +182,8 @@ Return JSON:
             category=result.get('category', category),
             options=result['options'],
             confidence=result.get('confidence', 0.8),
-            source_url=scraped['url']
+            source_url=scraped['url'],
+            resolution_date=result.get('resolution_date')
         )
         
     except Exception as e:
@@ -165,8 +198,14 @@ Return JSON:
             source_url=scraped['url']
         )
 
-def create_market(event: PredictionEvent) -> Optional[str]:
-    """Create market on blockchain"""
+def create_market(event: PredictionEvent, override_filter: bool = False) -> Optional[str]:
+    """Create market on blockchain; events blocked by the content filter are refused unless an admin upheld an appeal"""
+    moderation = content_filter.check_event(event.model_dump())
+    if moderation["blocked"] and not override_filter:
+        categories = ", ".join(m["category"] for m in moderation["matches"] if m["action"] == content_filter.ACTION_BLOCK)
+        print(f"🚫 Market refused by content filter ({categories})")
+        return None
+
     try:
         print(f"🔗 Creating market: {event.title}")
         
@@ -175,7 +214,9 @@ def create_market(event: PredictionEvent) -> Optional[str]:
             "description": event.description,
             "category": event.category,
             "options": event.options,
-            "source_url": event.source_url
+            "source_url": event.source_url,
+            "resolution_date": event.resolution_date,
+            "close_time": event.close_time
         }
         
         response = requests.post(
@@ -197,6 +238,13 @@ def create_market(event: PredictionEvent) -> Optional[str]:
         print(f"❌ Blockchain error: {e}")
         return None
 
+def require_admin(x_admin_token: Optional[str] = Header(None)) -> None:
+    """Review-queue actions require the X-Admin-Token header to match ADMIN_TOKEN"""
+    if not ADMIN_TOKEN:
+        raise HTTPException(status_code=403, detail="Admin actions disabled: ADMIN_TOKEN is not configured")
+    if not x_admin_token or not hmac.compare_digest(x_admin_token, ADMIN_TOKEN):
+        raise HTTPException(status_code=401, detail="Invalid or missing X-Admin-Token")
+
 # ============================================
 # API ENDPOINTS
 # ============================================
@@ -211,6 +259,8 @@ async def root():
         "blockchain": BLOCKCHAIN_URL,
         "endpoints": {
             "POST /scrape": "Scrape URL and create market",
+            "GET /scrape/events": "Review queue of scraped events",
+            "POST /snapshots": "Snapshot a resolution source URL",
             "GET /health": "Health check"
         }
     }
@@ -229,25 +279,81 @@ async def scrape_url(request: URLRequest) -> ScrapeResponse:
     
     try:
         # Step 1: Scrape content
-        scraped = scrape_content(str(request.url))
+        scraped = scrape_content(str(request.url), render_js=request.render_js)
+        
+        # Step 2: Skip articles already seen (same canonical URL or near-identical body)
+        if not request.allow_duplicates:
+            duplicate = dedupe.find_duplicate(scraped["url"], scraped["content"])
+            if duplicate:
+                return ScrapeResponse(
+                    success=False,
+                    message=f"⏭️ Duplicate article - already seen as {duplicate['canonical_url']}"
+                )
         
-        # Step 2: AI analysis
+        # Step 3: AI analysis
         event = analyze_with_ai(scraped, request.category or "tech")
+        event = PredictionEvent(**normalize_event_dates(event.model_dump()))
         
-        # Step 3: Create market
+        # Step 4: Persist for review - only trusted, good-quality events convert automatically
+        moderation = content_filter.check_event(event.model_dump())
+        scored = quality.score_event(event.model_dump(), trusted_source=event_store.is_trusted_source(event.source_url))
+        record = event_store.record_event(event.model_dump(), moderation=moderation, quality=scored)
+        # Only a real analysis retires the article; after an AI failure a retry should still go through
+        if not quality.is_ai_fallback(event.model_dump()):
+            dedupe.mark_seen(scraped["url"], scraped["content"])
+        if moderation["blocked"]:
+            return ScrapeResponse(
+                success=False,
+                event=event,
+                scraped_event_id=record["id"],
+                message="🚫 Event blocked by content filter - it can be appealed for admin review"
+            )
+        if moderation["flagged"]:
+            return ScrapeResponse(
+                success=True,
+                event=event,
+                scraped_event_id=record["id"],
+                message="🕒 Event flagged by content filter and queued for admin review"
+            )
+        if not record["trusted_source"]:
+            return ScrapeResponse(
+                success=True,
+                event=event,
+                scraped_event_id=record["id"],
+                message="🕒 Event queued for admin review (untrusted source)"
+            )
+        if not has_usable_close_time(event.model_dump()):
+            return ScrapeResponse(
+                success=True,
+                event=event,
+                scraped_event_id=record["id"],
+                message="🕒 Event queued for admin review (no usable future close time)"
+            )
+        if scored["draft"]:
+            return ScrapeResponse(
+                success=True,
+                event=event,
+                scraped_event_id=record["id"],
+                message=f"🕒 Event held as draft for admin review (quality score {scored['score']})"
+            )
+        
+        # Step 5: Create market
         market_id = create_market(event)
         
         if market_id:
+            event_store.update_status(record["id"], event_store.STATUS_CONVERTED, market_id=market_id)
             return ScrapeResponse(
                 success=True,
                 market_id=market_id,
                 event=event,
+                scraped_event_id=record["id"],
                 message=f"✅ Market created from {urlparse(str(request.url)).netloc}"
             )
         else:
             return ScrapeResponse(
                 success=False,
                 event=event,
+                scraped_event_id=record["id"],
                 message="⚠️ Event analyzed but market creation failed"
             )
             
@@ -257,6 +363,68 @@ async def scrape_url(request: URLRequest) -> ScrapeResponse:
             message=f"❌ Error: {str(e)}"
         )
 
+@app.get("/scrape/events", dependencies=[Depends(require_admin)])
+async def list_scraped_events(status: Optional[str] = None):
+    """Admin review queue of persisted scraped events"""
+    if status and status not in event_store.STATUSES:
+        raise HTTPException(status_code=400, detail=f"Unknown status: {status}")
+    events = event_store.list_events(status)
+    return {"count": len(events), "events": events}
+
+@app.post("/scrape/events/{event_id}/approve", dependencies=[Depends(require_admin)])
+async def approve_scraped_event(event_id: str):
+    """Approve a queued event and convert it into a market"""
+    record = event_store.get_event(event_id)
+    if not record:
+        raise HTTPException(status_code=404, detail=f"Unknown scraped event: {event_id}")
+    if record["status"] in (event_store.STATUS_CONVERTED, event_store.STATUS_REJECTED):
+        raise HTTPException(status_code=409, detail=f"Event already {record['status']}")
+    
+    # A blocked event can only reach approval through an upheld appeal
+    upheld_appeal = bool((record.get("moderation") or {}).get("blocked"))
+    event_store.update_status(event_id, event_store.STATUS_APPROVED)
+    market_id = create_market(PredictionEvent(**record["event"]), override_filter=upheld_appeal)
+    if market_id:
+        record = event_store.update_status(event_id, event_store.STATUS_CONVERTED, market_id=market_id)
+    else:
+        record = event_store.get_event(event_id)
+    return {"success": market_id is not None, "event": record}
+
+@app.post("/scrape/events/{event_id}/appeal")
+async def appeal_scraped_event(event_id: str):
+    """Appeal an event rejected by the content filter"""
+    try:
+        record = event_store.appeal(event_id)
+    except ValueError as e:
+        raise HTTPException(status_code=409, detail=str(e))
+    if not record:
+        raise HTTPException(status_code=404, detail=f"Unknown scraped event: {event_id}")
+    return {"success": True, "event": record}
+
+@app.post("/scrape/events/{event_id}/reject", dependencies=[Depends(require_admin)])
+async def reject_scraped_event(event_id: str):
+    """Reject a queued event"""
+    record = event_store.get_event(event_id)
+    if not record:
+        raise HTTPException(status_code=404, detail=f"Unknown scraped event: {event_id}")
+    if record["status"] in (event_store.STATUS_CONVERTED, event_store.STATUS_REJECTED):
+        raise HTTPException(status_code=409, detail=f"Event already {record['status']}")
+    
+    return {"success": True, "event": event_store.update_status(event_id, event_store.STATUS_REJECTED)}
+
+@app.post("/snapshots", dependencies=[Depends(require_admin)])
+async def create_snapshot(request: SnapshotRequest):
+    """Capture the cited resolution source at resolution time"""
+    return snapshots.capture_snapshot(str(request.url), market_id=request.market_id, render_js=request.render_js)
+
+@app.get("/snapshots/{snapshot_id}")
+async def get_snapshot(snapshot_id: str):
+    """Stored snapshot with extracted text and hash verification"""
+    snapshot = snapshots.load_snapshot(snapshot_id)
+    if not snapshot:
+        raise HTTPException(status_code=404, detail=f"Unknown snapshot: {snapshot_id}")
+    return snapshot
+
 # ============================================
 # MAIN
 # ============================================